@@ -1,16 +1,78 @@
 use std::time::{Duration, Instant};
 use chrono::{DateTime, Utc};
 use reqwest::blocking::Client;
-use std::io::Write;
+use std::io::{Read, Write};
 use std::fs::File;
 use std::sync::{mpsc, Arc};
 use std::thread;
-
+use std::collections::HashMap;
+use rand::Rng;
+use serde::{Serialize, Serializer};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::net::{TcpStream, ToSocketAddrs};
+
+#[derive(Serialize)]
 struct WebsiteStatus {
     url: String,
+    #[serde(serialize_with = "serialize_action_status")]
     action_status: Result<u16, String>,
+    #[serde(serialize_with = "serialize_duration_secs")]
     response_time: std::time::Duration,
+    #[serde(serialize_with = "serialize_duration_secs")]
+    time_to_first_byte: std::time::Duration,
     timestamp: DateTime<Utc>,
+    tls_expiry_days: Option<i64>,
+    #[serde(serialize_with = "serialize_assertion")]
+    assertion: Option<Result<(), String>>,
+}
+
+fn serialize_action_status<S>(status: &Result<u16, String>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match status {
+        Ok(code) => serializer.serialize_u16(*code),
+        Err(message) => serializer.serialize_str(message),
+    }
+}
+
+fn serialize_duration_secs<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_f64(duration.as_secs_f64())
+}
+
+fn serialize_assertion<S>(
+    assertion: &Option<Result<(), String>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match assertion {
+        Some(Ok(())) => serializer.serialize_none(),
+        Some(Err(reason)) => serializer.serialize_str(reason),
+        None => serializer.serialize_str("not evaluated"),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Json,
+    Csv,
+    Prometheus,
+}
+
+impl OutputFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Json => "json",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Prometheus => "prom",
+        }
+    }
 }
 
 struct Config {
@@ -19,6 +81,48 @@ struct Config {
     workers: usize,
     timeout_secs: u64,
     retries: usize,
+    backoff_base_ms: u64,
+    backoff_factor: f64,
+    backoff_max_ms: u64,
+    format: OutputFormat,
+    output: Option<String>,
+    watch: bool,
+    interval_secs: u64,
+    notify_webhook: Option<String>,
+    notify_secret: Option<String>,
+    cert_warn_days: Option<i64>,
+    expect_contains: Option<String>,
+    expect_status: Option<u16>,
+    probe_bytes: usize,
+}
+
+#[derive(Clone)]
+struct CheckOptions {
+    timeout: Duration,
+    retries: u32,
+    backoff_base_ms: u64,
+    backoff_factor: f64,
+    backoff_max_ms: u64,
+    cert_warn_days: Option<i64>,
+    expect_contains: Option<String>,
+    expect_status: Option<u16>,
+    probe_bytes: usize,
+}
+
+impl CheckOptions {
+    fn from_config(config: &Config) -> Self {
+        CheckOptions {
+            timeout: Duration::from_secs(config.timeout_secs),
+            retries: config.retries as u32,
+            backoff_base_ms: config.backoff_base_ms,
+            backoff_factor: config.backoff_factor,
+            backoff_max_ms: config.backoff_max_ms,
+            cert_warn_days: config.cert_warn_days,
+            expect_contains: config.expect_contains.clone(),
+            expect_status: config.expect_status,
+            probe_bytes: config.probe_bytes,
+        }
+    }
 }
 
 fn parse_args(args: &[String]) -> Result<Config, String> {
@@ -30,6 +134,19 @@ fn parse_args(args: &[String]) -> Result<Config, String> {
         .unwrap_or(4);
     let mut timeout_secs = 5;
     let mut retries = 3;
+    let mut backoff_base_ms = 100;
+    let mut backoff_factor = 2.0;
+    let mut backoff_max_ms = 5000;
+    let mut format = OutputFormat::Json;
+    let mut output = None;
+    let mut watch = false;
+    let mut interval_secs = 60;
+    let mut notify_webhook = None;
+    let mut notify_secret = None;
+    let mut cert_warn_days = None;
+    let mut expect_contains = None;
+    let mut expect_status = None;
+    let mut probe_bytes: usize = 64 * 1024;
 
     while let Some(arg) = args.next() {
         match arg.as_str() {
@@ -48,6 +165,58 @@ fn parse_args(args: &[String]) -> Result<Config, String> {
                 let r = args.next().ok_or("Missing value after --retries")?;
                 retries = r.parse().map_err(|_| "Invalid value for --retries")?;
             }
+            "--backoff-base" => {
+                let b = args.next().ok_or("Missing value after --backoff-base")?;
+                backoff_base_ms = b.parse().map_err(|_| "Invalid value for --backoff-base")?;
+            }
+            "--backoff-factor" => {
+                let f = args.next().ok_or("Missing value after --backoff-factor")?;
+                backoff_factor = f.parse().map_err(|_| "Invalid value for --backoff-factor")?;
+            }
+            "--backoff-max" => {
+                let m = args.next().ok_or("Missing value after --backoff-max")?;
+                backoff_max_ms = m.parse().map_err(|_| "Invalid value for --backoff-max")?;
+            }
+            "--format" => {
+                let f = args.next().ok_or("Missing value after --format")?;
+                format = match f.as_str() {
+                    "json" => OutputFormat::Json,
+                    "csv" => OutputFormat::Csv,
+                    "prometheus" => OutputFormat::Prometheus,
+                    _ => return Err(format!("Invalid value for --format: {f}")),
+                };
+            }
+            "--output" => {
+                output = Some(args.next().ok_or("Missing value after --output")?);
+            }
+            "--watch" => {
+                watch = true;
+            }
+            "--interval" => {
+                let i = args.next().ok_or("Missing value after --interval")?;
+                interval_secs = i.parse().map_err(|_| "Invalid value for --interval")?;
+            }
+            "--notify-webhook" => {
+                notify_webhook = Some(args.next().ok_or("Missing value after --notify-webhook")?);
+            }
+            "--notify-secret" => {
+                notify_secret = Some(args.next().ok_or("Missing value after --notify-secret")?);
+            }
+            "--cert-warn-days" => {
+                let d = args.next().ok_or("Missing value after --cert-warn-days")?;
+                cert_warn_days = Some(d.parse().map_err(|_| "Invalid value for --cert-warn-days")?);
+            }
+            "--expect-contains" => {
+                expect_contains = Some(args.next().ok_or("Missing value after --expect-contains")?);
+            }
+            "--expect-status" => {
+                let s = args.next().ok_or("Missing value after --expect-status")?;
+                expect_status = Some(s.parse().map_err(|_| "Invalid value for --expect-status")?);
+            }
+            "--probe-bytes" => {
+                let b = args.next().ok_or("Missing value after --probe-bytes")?;
+                probe_bytes = b.parse().map_err(|_| "Invalid value for --probe-bytes")?;
+            }
             _ if arg.starts_with("--") => {
                 return Err(format!("Unknown flag: {arg}"));
             }
@@ -67,9 +236,231 @@ fn parse_args(args: &[String]) -> Result<Config, String> {
         workers,
         timeout_secs,
         retries,
+        backoff_base_ms,
+        backoff_factor,
+        backoff_max_ms,
+        format,
+        output: output.cloned(),
+        watch,
+        interval_secs,
+        notify_webhook: notify_webhook.cloned(),
+        notify_secret: notify_secret.cloned(),
+        cert_warn_days,
+        expect_contains: expect_contains.cloned(),
+        expect_status,
+        probe_bytes,
     })
 }
 
+fn backoff_delay(attempt: u32, base_ms: u64, factor: f64, max_ms: u64) -> Duration {
+    let raw = base_ms as f64 * factor.powi(attempt as i32);
+    let delay_ms = raw.min(max_ms as f64).max(0.0);
+    let jitter_ms = if delay_ms > 0.0 {
+        rand::thread_rng().gen_range(0.0..=(delay_ms / 2.0))
+    } else {
+        0.0
+    };
+    Duration::from_millis((delay_ms + jitter_ms) as u64)
+}
+
+fn render_json(statuses: &[WebsiteStatus]) -> String {
+    serde_json::to_string_pretty(statuses).expect("Failed to serialize statuses as JSON")
+}
+
+fn render_csv(statuses: &[WebsiteStatus]) -> String {
+    let mut out = String::from(
+        "url,status,response_time,time_to_first_byte,timestamp,tls_expiry_days,assertion\n",
+    );
+    for status in statuses {
+        let status_field = match &status.action_status {
+            Ok(code) => code.to_string(),
+            Err(message) => message.clone(),
+        };
+        let tls_field = status
+            .tls_expiry_days
+            .map_or(String::new(), |days| days.to_string());
+        let assertion_field = match &status.assertion {
+            Some(Ok(())) => String::new(),
+            Some(Err(reason)) => reason.clone(),
+            None => "not evaluated".to_string(),
+        };
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            csv_escape(&status.url),
+            csv_escape(&status_field),
+            status.response_time.as_secs_f64(),
+            status.time_to_first_byte.as_secs_f64(),
+            status.timestamp.to_rfc3339(),
+            tls_field,
+            csv_escape(&assertion_field),
+        ));
+    }
+    out
+}
+
+fn csv_escape(field: &str) -> String {
+    if field.contains(',') || field.contains('"') || field.contains('\n') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn prometheus_escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn render_prometheus(statuses: &[WebsiteStatus]) -> String {
+    let mut out = String::new();
+    out.push_str("# HELP website_up Whether the website responded successfully (1) or not (0).\n");
+    out.push_str("# TYPE website_up gauge\n");
+    for status in statuses {
+        let url = prometheus_escape(&status.url);
+        let up = if status.action_status.is_ok() { 1 } else { 0 };
+        out.push_str(&format!("website_up{{url=\"{url}\"}} {up}\n"));
+    }
+    out.push_str("# HELP website_response_seconds Response time of the last check, in seconds.\n");
+    out.push_str("# TYPE website_response_seconds gauge\n");
+    for status in statuses {
+        let url = prometheus_escape(&status.url);
+        out.push_str(&format!(
+            "website_response_seconds{{url=\"{url}\"}} {}\n",
+            status.response_time.as_secs_f64()
+        ));
+    }
+    out.push_str("# HELP website_status_code HTTP status code of the last successful check.\n");
+    out.push_str("# TYPE website_status_code gauge\n");
+    for status in statuses {
+        if let Ok(code) = status.action_status {
+            let url = prometheus_escape(&status.url);
+            out.push_str(&format!("website_status_code{{url=\"{url}\"}} {code}\n"));
+        }
+    }
+    out.push_str("# HELP website_tls_cert_expiry_days Days until the HTTPS certificate expires.\n");
+    out.push_str("# TYPE website_tls_cert_expiry_days gauge\n");
+    for status in statuses {
+        if let Some(days) = status.tls_expiry_days {
+            let url = prometheus_escape(&status.url);
+            out.push_str(&format!("website_tls_cert_expiry_days{{url=\"{url}\"}} {days}\n"));
+        }
+    }
+    out.push_str("# HELP website_assertion_passed Whether --expect-contains/--expect-status matched (1) or not (0); absent if never evaluated.\n");
+    out.push_str("# TYPE website_assertion_passed gauge\n");
+    for status in statuses {
+        if let Some(assertion) = &status.assertion {
+            let url = prometheus_escape(&status.url);
+            let passed = if assertion.is_ok() { 1 } else { 0 };
+            out.push_str(&format!("website_assertion_passed{{url=\"{url}\"}} {passed}\n"));
+        }
+    }
+    out.push_str("# HELP website_ttfb_seconds Time to first response byte, in seconds.\n");
+    out.push_str("# TYPE website_ttfb_seconds gauge\n");
+    for status in statuses {
+        let url = prometheus_escape(&status.url);
+        out.push_str(&format!(
+            "website_ttfb_seconds{{url=\"{url}\"}} {}\n",
+            status.time_to_first_byte.as_secs_f64()
+        ));
+    }
+    out
+}
+
+fn render_statuses(statuses: &[WebsiteStatus], format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Json => render_json(statuses),
+        OutputFormat::Csv => render_csv(statuses),
+        OutputFormat::Prometheus => render_prometheus(statuses),
+    }
+}
+
+#[derive(Serialize)]
+struct NotifyEvent {
+    url: String,
+    old_status: String,
+    new_status: String,
+    timestamp: DateTime<Utc>,
+}
+
+fn status_summary(status: &Result<u16, String>) -> String {
+    match status {
+        Ok(code) => code.to_string(),
+        Err(message) => message.clone(),
+    }
+}
+
+fn status_class(status: &Result<u16, String>) -> String {
+    match status {
+        Ok(code) => format!("{}xx", code / 100),
+        Err(_) => "down".to_string(),
+    }
+}
+
+// Matches the X-Signature-256: sha256=<hex> scheme used by build-o-tron's webhook verification.
+fn sign_payload(secret: &str, payload: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(payload);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+fn send_notification(
+    client: &Client,
+    webhook: &str,
+    secret: &str,
+    event: &NotifyEvent,
+    timeout: Duration,
+) {
+    let payload = serde_json::to_vec(event).expect("Failed to serialize notify event");
+    let signature = sign_payload(secret, &payload);
+
+    let result = client
+        .post(webhook)
+        .timeout(timeout)
+        .header("X-Signature-256", format!("sha256={signature}"))
+        .header("Content-Type", "application/json")
+        .body(payload)
+        .send();
+
+    if let Err(err) = result {
+        eprintln!("Failed to deliver webhook notification for {}: {err}", event.url);
+    }
+}
+
+fn notify_changes(
+    client: &Client,
+    config: &Config,
+    previous: &HashMap<String, Result<u16, String>>,
+    statuses: &[WebsiteStatus],
+) {
+    let (webhook, secret) = match (&config.notify_webhook, &config.notify_secret) {
+        (Some(webhook), Some(secret)) => (webhook, secret),
+        _ => return,
+    };
+
+    for status in statuses {
+        if let Some(old_status) = previous.get(&status.url) {
+            if status_class(old_status) != status_class(&status.action_status) {
+                let event = NotifyEvent {
+                    url: status.url.clone(),
+                    old_status: status_summary(old_status),
+                    new_status: status_summary(&status.action_status),
+                    timestamp: status.timestamp,
+                };
+                send_notification(
+                    client,
+                    webhook,
+                    secret,
+                    &event,
+                    Duration::from_secs(config.timeout_secs),
+                );
+            }
+        }
+    }
+}
+
 fn read_urls_from_file(path: &str) -> Result<Vec<String>, String> {
     let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
     Ok(content
@@ -79,29 +470,149 @@ fn read_urls_from_file(path: &str) -> Result<Vec<String>, String> {
         .collect())
 }
 
-fn check_website(
-    client: &Client,
-    url: &str,
-    timeout: Duration,
-    retries: u32,
-) -> WebsiteStatus {
-    let start = Instant::now();
+fn extract_https_host(url: &str) -> Option<(String, u16)> {
+    let rest = url.strip_prefix("https://")?;
+    let host_port = rest.split('/').next().unwrap_or(rest);
+    let mut parts = host_port.splitn(2, ':');
+    let host = parts.next().unwrap_or(host_port);
+    let port = match parts.next() {
+        Some(port_str) => port_str.parse().ok()?,
+        None => 443,
+    };
+    if host.is_empty() {
+        None
+    } else {
+        Some((host.to_string(), port))
+    }
+}
+
+fn fetch_tls_expiry_days(url: &str, timeout: Duration) -> Option<i64> {
+    let (host, port) = extract_https_host(url)?;
+
+    let mut root_store = rustls::RootCertStore::empty();
+    root_store.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
+        rustls::OwnedTrustAnchor::from_subject_spki_name_constraints(
+            ta.subject,
+            ta.spki,
+            ta.name_constraints,
+        )
+    }));
+    let config = rustls::ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(root_store)
+        .with_no_client_auth();
+
+    let server_name = host.as_str().try_into().ok()?;
+    let mut conn = rustls::ClientConnection::new(Arc::new(config), server_name).ok()?;
+    let addr = (host.as_str(), port).to_socket_addrs().ok()?.next()?;
+    let mut sock = TcpStream::connect_timeout(&addr, timeout).ok()?;
+    sock.set_read_timeout(Some(timeout)).ok()?;
+    sock.set_write_timeout(Some(timeout)).ok()?;
+    let mut tls = rustls::Stream::new(&mut conn, &mut sock);
+    tls.flush().ok()?;
+
+    let leaf = conn.peer_certificates()?.first()?;
+    let (_, cert) = x509_parser::parse_x509_certificate(leaf.as_ref()).ok()?;
+    let not_after = DateTime::from_timestamp(cert.validity().not_after.timestamp(), 0)?;
+
+    Some((not_after - Utc::now()).num_days())
+}
+
+struct ProbeResponse {
+    status_code: u16,
+    time_to_first_byte: Duration,
+    body: String,
+}
+
+fn probe_url(client: &Client, url: &str, timeout: Duration, probe_bytes: usize) -> reqwest::Result<ProbeResponse> {
+    let range = format!("bytes=0-{}", probe_bytes.saturating_sub(1));
+    let ttfb_start = Instant::now();
+    let resp = client.get(url).timeout(timeout).header("Range", range).send()?;
+    let time_to_first_byte = ttfb_start.elapsed();
+    let status_code = resp.status().as_u16();
+
+    let mut buf = Vec::new();
+    let _ = resp.take(probe_bytes as u64).read_to_end(&mut buf);
+    let body = String::from_utf8_lossy(&buf).into_owned();
+
+    Ok(ProbeResponse {
+        status_code,
+        time_to_first_byte,
+        body,
+    })
+}
+
+fn evaluate_assertion(
+    probe: &ProbeResponse,
+    expect_status: Option<u16>,
+    expect_contains: Option<&str>,
+) -> Result<(), String> {
+    if let Some(expected) = expect_status {
+        if probe.status_code != expected {
+            return Err(format!(
+                "expected status {expected}, got {}",
+                probe.status_code
+            ));
+        }
+    }
+    if let Some(substring) = expect_contains {
+        if !probe.body.contains(substring) {
+            return Err(format!("response body did not contain {substring:?}"));
+        }
+    }
+    Ok(())
+}
+
+fn check_website(client: &Client, url: &str, opts: &CheckOptions) -> WebsiteStatus {
     let mut last_err = None;
+    let mut last_attempt_time = Duration::default();
 
-    for _ in 0..=retries {
-        let response = client.get(url).timeout(timeout).send();
+    for attempt in 0..=opts.retries {
+        let attempt_start = Instant::now();
+        let response = probe_url(client, url, opts.timeout, opts.probe_bytes);
         match response {
-            Ok(resp) => {
-                let duration = start.elapsed();
+            Ok(probe) => {
+                let response_time = attempt_start.elapsed();
+
+                let tls_expiry_days = if url.starts_with("https://") {
+                    fetch_tls_expiry_days(url, opts.timeout)
+                } else {
+                    None
+                };
+
+                let assertion = evaluate_assertion(
+                    &probe,
+                    opts.expect_status,
+                    opts.expect_contains.as_deref(),
+                );
+
+                let action_status = match (opts.cert_warn_days, tls_expiry_days) {
+                    (Some(warn_days), Some(days_left)) if days_left <= warn_days => Err(format!(
+                        "TLS certificate for {url} expires in {days_left} day(s), within the {warn_days}-day warning threshold"
+                    )),
+                    _ if assertion.is_err() => {
+                        Err(assertion.clone().unwrap_err())
+                    }
+                    _ => Ok(probe.status_code),
+                };
+
                 return WebsiteStatus {
                     url: url.to_string(),
-                    action_status: Ok(resp.status().as_u16()),
-                    response_time: duration,
+                    action_status,
+                    response_time,
+                    time_to_first_byte: probe.time_to_first_byte,
                     timestamp: Utc::now(),
+                    tls_expiry_days,
+                    assertion: Some(assertion),
                 };
             }
             Err(err) => {
+                last_attempt_time = attempt_start.elapsed();
                 last_err = Some(err);
+                if attempt < opts.retries {
+                    let delay = backoff_delay(attempt, opts.backoff_base_ms, opts.backoff_factor, opts.backoff_max_ms);
+                    thread::sleep(delay);
+                }
             }
         }
     }
@@ -109,10 +620,13 @@ fn check_website(
     WebsiteStatus {
         url: url.to_string(),
         action_status: Err(format!(
-            "Error: {}", 
+            "Error: {}",
             last_err.map_or_else(|| "unknown error".to_string(), |e| e.to_string()))),
-        response_time: start.elapsed(),
+        response_time: last_attempt_time,
+        time_to_first_byte: Duration::default(),
         timestamp: Utc::now(),
+        tls_expiry_days: None,
+        assertion: None,
     }
 }
 
@@ -120,32 +634,23 @@ fn check_website(
 
 
 
-fn main() {
-    let args: Vec<String> = std::env::args().collect();
-    let config = parse_args(&args).expect("Invalid arguments");
-
-    let mut urls = config.urls.clone();
-    if let Some(file_path) = &config.file {
-        let mut file_urls = read_urls_from_file(file_path).expect("Failed to read URLs from file");
-        urls.append(&mut file_urls);
-    }
-
-    let client = Arc::new(Client::new());
+fn run_pass(config: &Config, client: &Arc<Client>, urls: &Arc<Vec<String>>) -> Vec<WebsiteStatus> {
     let (tx, rx) = mpsc::channel();
-    let urls = Arc::new(urls); // Share URLs with threads
 
     // Distribute the work
+    let opts = CheckOptions::from_config(config);
+
     for i in 0..config.workers {
         let tx = tx.clone();
-        let client = Arc::clone(&client);
-        let urls = Arc::clone(&urls);
-        let timeout = Duration::from_secs(config.timeout_secs);
-        let retries = config.retries as u32;
+        let client = Arc::clone(client);
+        let urls = Arc::clone(urls);
+        let workers = config.workers;
+        let opts = opts.clone();
 
         thread::spawn(move || {
-            for j in (i..urls.len()).step_by(config.workers) {
+            for j in (i..urls.len()).step_by(workers) {
                 let url = &urls[j];
-                let status = check_website(&client, url, timeout, retries);
+                let status = check_website(&client, url, &opts);
                 tx.send(status).expect("Failed to send result");
             }
         });
@@ -158,26 +663,58 @@ fn main() {
     for received in rx {
         statuses.push(received);
     }
+    statuses
+}
 
-    let status_strings: Vec<String> = statuses
-        .iter()
-        .map(|status| {
-            format!(
-                "{{\"url\": \"{}\", \"status\": \"{}\", \"response_time\": \"{}\", \"timestamp\": \"{}\"}}",
-                status.url,
-                status.action_status.as_ref().map_or("unknown error".to_string(), |s| s.to_string()),
-                status.response_time.as_secs(),
-                status.timestamp.to_rfc3339()
-            )
-        })
-        .collect();
+fn write_output(config: &Config, statuses: &[WebsiteStatus]) {
+    let final_output = render_statuses(statuses, config.format);
 
-    let final_output = format!("[{}]", status_strings.join(",\n"));
+    let output_path = config
+        .output
+        .clone()
+        .unwrap_or_else(|| format!("status.{}", config.format.extension()));
 
-    let mut file = File::create("status.json").expect("Unable to create file");
+    let mut file = File::create(&output_path).expect("Unable to create file");
     file.write_all(final_output.as_bytes()).expect("Unable to write data");
 
-    println!("Output written to status_output.txt");
+    println!("Output written to {output_path}");
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let config = parse_args(&args).expect("Invalid arguments");
+
+    let mut urls = config.urls.clone();
+    if let Some(file_path) = &config.file {
+        let mut file_urls = read_urls_from_file(file_path).expect("Failed to read URLs from file");
+        urls.append(&mut file_urls);
+    }
+
+    let client = Arc::new(Client::new());
+    let urls = Arc::new(urls); // Share URLs with threads
+
+    if !config.watch {
+        let statuses = run_pass(&config, &client, &urls);
+        write_output(&config, &statuses);
+        if statuses.iter().any(|status| status.action_status.is_err()) {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let mut previous: HashMap<String, Result<u16, String>> = HashMap::new();
+    loop {
+        let statuses = run_pass(&config, &client, &urls);
+        notify_changes(&client, &config, &previous, &statuses);
+
+        previous = statuses
+            .iter()
+            .map(|status| (status.url.clone(), status.action_status.clone()))
+            .collect();
+
+        write_output(&config, &statuses);
+        thread::sleep(Duration::from_secs(config.interval_secs));
+    }
 }
 
 
@@ -201,8 +738,6 @@ mod tests {
             "--retries".to_string(),
             "3".to_string(),
             "https://example.com".to_string(),
-            assert_eq!(config.timeout_secs, 10);
-            assert_eq!(config.retries, 3); 
         ];
 
         let result = parse_args(&args);
@@ -233,8 +768,90 @@ mod tests {
     fn test_check_website_success() {
         let client = Client::new();
         let url = "https://example.com";
-        let status = check_website(&client, url, Duration::from_secs(5), 3);
+        let opts = CheckOptions {
+            timeout: Duration::from_secs(5),
+            retries: 3,
+            backoff_base_ms: 100,
+            backoff_factor: 2.0,
+            backoff_max_ms: 5000,
+            cert_warn_days: None,
+            expect_contains: None,
+            expect_status: None,
+            probe_bytes: 64 * 1024,
+        };
+        let status = check_website(&client, url, &opts);
         assert!(status.action_status.is_ok());
         assert_eq!(status.url, url.to_string());
     }
+
+    #[test]
+    fn test_sign_payload_known_vector() {
+        let signature = sign_payload("secret", b"hello");
+        assert_eq!(
+            signature,
+            "88aab3ede8d3adf94d26ab90d3bafd4a2083070c3bcce9c014ee04a443847c0b"
+        );
+    }
+
+    #[test]
+    fn test_backoff_delay_caps_at_max() {
+        let delay = backoff_delay(10, 100, 2.0, 5000);
+        assert!(delay <= Duration::from_millis(5000 + 5000 / 2));
+    }
+
+    #[test]
+    fn test_backoff_delay_grows_with_attempt() {
+        let first = backoff_delay(0, 100, 2.0, 5000);
+        let third = backoff_delay(2, 100, 2.0, 5000);
+        assert!(third >= first);
+    }
+
+    #[test]
+    fn test_csv_escape() {
+        assert_eq!(csv_escape("plain"), "plain");
+        assert_eq!(csv_escape("a,b"), "\"a,b\"");
+        assert_eq!(csv_escape("a\"b"), "\"a\"\"b\"");
+    }
+
+    #[test]
+    fn test_prometheus_escape() {
+        assert_eq!(prometheus_escape("plain"), "plain");
+        assert_eq!(prometheus_escape("a\\b"), "a\\\\b");
+        assert_eq!(prometheus_escape("a\"b"), "a\\\"b");
+        assert_eq!(prometheus_escape("a\nb"), "a\\nb");
+    }
+
+    #[test]
+    fn test_status_class() {
+        assert_eq!(status_class(&Ok(200)), "2xx");
+        assert_eq!(status_class(&Ok(404)), "4xx");
+        assert_eq!(status_class(&Err("timeout".to_string())), "down");
+    }
+
+    #[test]
+    fn test_extract_https_host() {
+        assert_eq!(
+            extract_https_host("https://example.com/path"),
+            Some(("example.com".to_string(), 443))
+        );
+        assert_eq!(
+            extract_https_host("https://example.com:8443/path"),
+            Some(("example.com".to_string(), 8443))
+        );
+        assert_eq!(extract_https_host("http://example.com"), None);
+    }
+
+    #[test]
+    fn test_evaluate_assertion() {
+        let probe = ProbeResponse {
+            status_code: 200,
+            time_to_first_byte: Duration::default(),
+            body: "hello world".to_string(),
+        };
+
+        assert!(evaluate_assertion(&probe, None, None).is_ok());
+        assert!(evaluate_assertion(&probe, Some(200), Some("hello")).is_ok());
+        assert!(evaluate_assertion(&probe, Some(404), None).is_err());
+        assert!(evaluate_assertion(&probe, None, Some("missing")).is_err());
+    }
 }
\ No newline at end of file